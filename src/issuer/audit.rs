@@ -0,0 +1,179 @@
+use keri::{
+    derivation::self_addressing::SelfAddressing, event::event_data::EventData,
+    prefix::SelfAddressingPrefix,
+};
+use teliox::event::verifiable_event::VerifiableEvent;
+
+use crate::error::Error;
+
+/// The first problem an audit pass ran into, pinpointing where a KEL/TEL
+/// log stopped being internally consistent.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Inconsistency {
+    /// The KEL event at `sn` does not chain from the digest of the event
+    /// before it.
+    BrokenKelLink { sn: u64 },
+    /// A TEL event for `vc` does not chain from the previous TEL event for
+    /// the same credential.
+    BrokenTelLink { vc: SelfAddressingPrefix, sn: u64 },
+    /// A TEL event's source seal does not resolve to a KEL interaction
+    /// event at the claimed sn and digest.
+    UnanchoredTelEvent { vc: SelfAddressingPrefix, sn: u64 },
+    /// A credential's TEL has more than one issuance, a revocation before
+    /// an issuance, or more than one revocation.
+    OutOfOrderIssueRevoke { vc: SelfAddressingPrefix },
+}
+
+/// The result of a full audit pass: how much was checked, and the first
+/// inconsistency found, if any.
+#[derive(Clone, Debug, Default)]
+pub struct AuditReport {
+    pub kel_events_checked: usize,
+    pub tel_events_checked: usize,
+    pub first_inconsistency: Option<Inconsistency>,
+}
+
+impl AuditReport {
+    pub fn is_clean(&self) -> bool {
+        self.first_inconsistency.is_none()
+    }
+
+    fn record(&mut self, inconsistency: Inconsistency) {
+        if self.first_inconsistency.is_none() {
+            self.first_inconsistency = Some(inconsistency);
+        }
+    }
+}
+
+/// Walks a single credential's TEL slice (as produced by `Controller::get_tel`)
+/// with no database access, checking that the issue/revoke ordering is
+/// monotonic: at most one issuance, and a revocation only ever after it.
+/// Used as the per-credential half of [`super::Controller::verify_log`];
+/// it can also be run directly on a [`super::CredentialPresentation`]'s
+/// bundled slice.
+pub fn verify_slice(events: &[VerifiableEvent]) -> AuditReport {
+    let mut report = AuditReport::default();
+    let mut issued = false;
+    let mut revoked = false;
+
+    for event in events {
+        report.tel_events_checked += 1;
+        let vc_event = match &event.event {
+            teliox::event::Event::Vc(vc_event) => vc_event,
+            // Management (backer) events don't carry issue/revoke ordering.
+            teliox::event::Event::Management(_) => continue,
+        };
+        // A credential's TEL only ever holds an issuance at sn 0 followed by
+        // at most one revocation at sn 1; any other sn can't be either one
+        // and is an inconsistency in its own right, not just a candidate
+        // revocation to wave through.
+        let is_revocation = match vc_event.sn {
+            0 => false,
+            1 => true,
+            _ => {
+                report.record(Inconsistency::OutOfOrderIssueRevoke {
+                    vc: vc_event.prefix.clone(),
+                });
+                break;
+            }
+        };
+
+        if is_revocation {
+            if !issued || revoked {
+                report.record(Inconsistency::OutOfOrderIssueRevoke {
+                    vc: vc_event.prefix.clone(),
+                });
+                break;
+            }
+            revoked = true;
+        } else {
+            if issued {
+                report.record(Inconsistency::OutOfOrderIssueRevoke {
+                    vc: vc_event.prefix.clone(),
+                });
+                break;
+            }
+            issued = true;
+        }
+    }
+
+    report
+}
+
+/// Confirms that a TEL event's source seal resolves to the KEL interaction
+/// event it claims to: the event at `seal.sn` in `kel` must actually be an
+/// interaction event (not an inception or rotation, which never carry TEL
+/// anchoring seals), and must re-derive to `seal.digest`.
+pub fn anchors_to(
+    kel: &[keri::event_message::SignedEventMessage],
+    seal: &teliox::seal::EventSourceSeal,
+) -> Result<bool, Error> {
+    let anchor = kel
+        .iter()
+        .find(|event| event.event_message.event.sn == seal.sn);
+    match anchor {
+        Some(event) => {
+            if !matches!(event.event_message.event.event_data, EventData::Ixn(_)) {
+                return Ok(false);
+            }
+            let digest = SelfAddressing::Blake3_256.derive(&event.event_message.serialize()?);
+            Ok(digest == seal.digest)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use keri::{derivation::self_addressing::SelfAddressing, signer::CryptoBox};
+
+    use super::verify_slice;
+    use crate::{error::Error, issuer::Controller};
+
+    #[test]
+    fn test_verify_slice_clean_issue_then_revoke() -> Result<(), Error> {
+        use tempfile::Builder;
+        let root = Builder::new().prefix("audit-test-db").tempdir().unwrap();
+        let tel_root = Builder::new()
+            .prefix("audit-tel-test-db")
+            .tempdir()
+            .unwrap();
+        let km = CryptoBox::new()?;
+
+        let mut controller =
+            Controller::init(root.path(), tel_root.path(), &km, Some(vec![]), 0, None)?;
+        let message = "some vc";
+        controller.issue(message, 0, &km)?;
+
+        let message_hash = SelfAddressing::Blake3_256.derive(message.as_bytes());
+        let report = verify_slice(&controller.get_tel(&message_hash)?);
+        assert!(report.is_clean());
+
+        controller.revoke(message, &km)?;
+        let report = verify_slice(&controller.get_tel(&message_hash)?);
+        assert!(report.is_clean());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_log_clean() -> Result<(), Error> {
+        use tempfile::Builder;
+        let root = Builder::new().prefix("audit-log-test-db").tempdir().unwrap();
+        let tel_root = Builder::new()
+            .prefix("audit-log-tel-test-db")
+            .tempdir()
+            .unwrap();
+        let km = CryptoBox::new()?;
+
+        let mut controller =
+            Controller::init(root.path(), tel_root.path(), &km, Some(vec![]), 0, None)?;
+        controller.issue("some vc", 0, &km)?;
+        controller.revoke("some vc", &km)?;
+
+        let report = controller.verify_log()?;
+        assert!(report.is_clean());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use aes::Aes128;
+use aes_gcm::{
+    aead::{Aead, NewAead},
+    Aes256Gcm, Key, Nonce,
+};
+use ctr::{
+    cipher::{NewCipher, StreamCipher},
+    Ctr128BE,
+};
+use rand::RngCore;
+use scrypt::{scrypt, Params as ScryptParams};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+type Aes128Ctr = Ctr128BE<Aes128>;
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+/// Bumped whenever the sealed record layout or KDF changes, so an older
+/// record can still be told apart from a newer one on read.
+const SEAL_VERSION: u8 = 1;
+
+/// Cheaper than [`crate::issuer::keystore`]'s scrypt params: this key is
+/// re-derived on every unlock rather than once at export time, but it still
+/// gates the same passphrase against offline guessing, so it needs real
+/// key-stretching rather than a bare hash.
+const SCRYPT_LOG_N: u8 = 12;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// An authenticated, versioned envelope around a ciphertext, written in
+/// place of a plaintext record wherever encryption-at-rest is enabled.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SealedRecord {
+    pub version: u8,
+    pub nonce: [u8; NONCE_LEN],
+    pub ciphertext: Vec<u8>,
+}
+
+/// Derives a 256-bit data-encryption key from a passphrase and salt via
+/// scrypt, the same key-stretching [`crate::issuer::keystore`] uses for the
+/// exported keystore file (at a lower cost factor, since this is re-derived
+/// on every unlock rather than once at export time). The passphrase's
+/// strength is exactly what's at stake against an offline guessing attack,
+/// so a bare salted hash isn't enough here either.
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; KEY_LEN], Error> {
+    let params = ScryptParams::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)
+        .map_err(|e| Error::Generic(format!("Invalid scrypt params: {}", e)))?;
+    let mut key = [0u8; KEY_LEN];
+    scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| Error::Generic(format!("scrypt derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Seals `plaintext` under `key` with a fresh random nonce.
+pub fn seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<SealedRecord, Error> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new(Key::from_slice(key));
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|e| Error::Generic(format!("Failed to seal record: {}", e)))?;
+    Ok(SealedRecord {
+        version: SEAL_VERSION,
+        nonce,
+        ciphertext,
+    })
+}
+
+/// Applies AES-128-CTR keystream `xor` to `data` under `key`/`iv`, used by
+/// [`crate::issuer::keystore`] to match the Ethereum JSON keystore format.
+pub(crate) fn xor_stream(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut buf = data.to_vec();
+    let mut cipher = Aes128Ctr::new(key.into(), iv.into());
+    cipher.apply_keystream(&mut buf);
+    buf
+}
+
+/// Unseals a [`SealedRecord`] previously produced by [`seal`].
+pub fn unseal(key: &[u8; KEY_LEN], record: &SealedRecord) -> Result<Vec<u8>, Error> {
+    if record.version != SEAL_VERSION {
+        return Err(Error::Generic(format!(
+            "Unsupported sealed record version {}",
+            record.version
+        )));
+    }
+    let cipher = Aes256Gcm::new(Key::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(&record.nonce), record.ciphertext.as_ref())
+        .map_err(|e| Error::Generic(format!("Failed to unseal record: {}", e)))
+}
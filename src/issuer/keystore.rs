@@ -0,0 +1,125 @@
+use std::{fs, path::Path};
+
+use rand::RngCore;
+use scrypt::{scrypt, Params as ScryptParams};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+const SCRYPT_LOG_N: u8 = 14;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const DK_LEN: usize = 32;
+
+/// An exported signing key, encrypted with a passphrase in the style of the
+/// Ethereum JSON keystore: scrypt for key derivation, AES-128-CTR for
+/// confidentiality, and an HMAC-like MAC so a wrong passphrase is rejected
+/// up front instead of yielding garbage key material.
+#[derive(Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub crypto: CryptoParams,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CryptoParams {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct KdfParams {
+    pub salt: String,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    pub dklen: usize,
+}
+
+fn scrypt_derive(passphrase: &str, salt: &[u8]) -> Result<[u8; DK_LEN], Error> {
+    let params = ScryptParams::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)
+        .map_err(|e| Error::Generic(format!("Invalid scrypt params: {}", e)))?;
+    let mut dk = [0u8; DK_LEN];
+    scrypt(passphrase.as_bytes(), salt, &params, &mut dk)
+        .map_err(|e| Error::Generic(format!("scrypt derivation failed: {}", e)))?;
+    Ok(dk)
+}
+
+fn mac_of(derived_key: &[u8], ciphertext: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(&derived_key[16..]);
+    hasher.update(ciphertext);
+    hex::encode(hasher.finalize())
+}
+
+/// Encrypts `secret` (raw signing key material) under `passphrase`.
+pub fn encrypt_secret(secret: &[u8], passphrase: &str) -> Result<Keystore, Error> {
+    let mut salt = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let mut iv = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut iv);
+
+    let derived_key = scrypt_derive(passphrase, &salt)?;
+    let ciphertext = crate::issuer::crypto::xor_stream(&derived_key[..16], &iv, secret);
+    let mac = mac_of(&derived_key, &ciphertext);
+
+    Ok(Keystore {
+        version: 1,
+        crypto: CryptoParams {
+            cipher: "aes-128-ctr".into(),
+            cipherparams: CipherParams { iv: hex::encode(iv) },
+            ciphertext: hex::encode(ciphertext),
+            kdf: "scrypt".into(),
+            kdfparams: KdfParams {
+                salt: hex::encode(salt),
+                log_n: SCRYPT_LOG_N,
+                r: SCRYPT_R,
+                p: SCRYPT_P,
+                dklen: DK_LEN,
+            },
+            mac,
+        },
+    })
+}
+
+/// Decrypts a [`Keystore`] produced by [`encrypt_secret`], rejecting the
+/// wrong passphrase via the embedded MAC rather than returning garbage.
+pub fn decrypt_secret(keystore: &Keystore, passphrase: &str) -> Result<Vec<u8>, Error> {
+    let salt = hex::decode(&keystore.crypto.kdfparams.salt)
+        .map_err(|e| Error::Generic(format!("Invalid keystore salt: {}", e)))?;
+    let iv = hex::decode(&keystore.crypto.cipherparams.iv)
+        .map_err(|e| Error::Generic(format!("Invalid keystore iv: {}", e)))?;
+    let ciphertext = hex::decode(&keystore.crypto.ciphertext)
+        .map_err(|e| Error::Generic(format!("Invalid keystore ciphertext: {}", e)))?;
+
+    let derived_key = scrypt_derive(passphrase, &salt)?;
+    if mac_of(&derived_key, &ciphertext) != keystore.crypto.mac {
+        return Err(Error::Generic("Incorrect passphrase".into()));
+    }
+
+    Ok(crate::issuer::crypto::xor_stream(&derived_key[..16], &iv, &ciphertext))
+}
+
+/// Encrypts `secret` and writes it to `path` as a keystore JSON file.
+pub fn export_to_file(path: &Path, secret: &[u8], passphrase: &str) -> Result<(), Error> {
+    let keystore = encrypt_secret(secret, passphrase)?;
+    let json = serde_json::to_vec_pretty(&keystore).map_err(|e| Error::Generic(e.to_string()))?;
+    fs::write(path, json).map_err(|e| Error::Generic(e.to_string()))
+}
+
+/// Reads and decrypts a keystore JSON file written by [`export_to_file`].
+pub fn import_from_file(path: &Path, passphrase: &str) -> Result<Vec<u8>, Error> {
+    let json = fs::read(path).map_err(|e| Error::Generic(e.to_string()))?;
+    let keystore: Keystore = serde_json::from_slice(&json).map_err(|e| Error::Generic(e.to_string()))?;
+    decrypt_secret(&keystore, passphrase)
+}
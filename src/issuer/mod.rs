@@ -1,4 +1,16 @@
-use std::path::{Path, PathBuf};
+mod audit;
+mod crypto;
+mod facade;
+mod keystore;
+mod policy;
+mod presentation;
+mod signature;
+mod witness;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::{error::Error, kerl::KERL, tel::Tel};
 use keri::{
@@ -8,6 +20,7 @@ use keri::{
     prefix::{BasicPrefix, IdentifierPrefix, Prefix, SelfAddressingPrefix},
     signer::KeyManager,
 };
+use rand::RngCore;
 use teliox::{
     database::EventDatabase,
     event::{manager_event::Config, verifiable_event::VerifiableEvent, Event},
@@ -15,24 +28,182 @@ use teliox::{
     state::vc_state::TelState,
 };
 
+pub use audit::{verify_slice, AuditReport, Inconsistency};
+pub use facade::Facade;
+pub use policy::AccessPolicy;
+pub use presentation::{verify_presentation, CredentialPresentation};
+pub use signature::IndexedSignature;
+pub use witness::{BackerTransport, NullTransport, OobiManager, ReceiptStore, WitnessReceipt};
+
+const KDF_SALT_FILE: &str = ".kdf-salt";
+/// File the encrypted KEL/TEL snapshot is sealed to, inside `root`, when a
+/// controller is opened with a passphrase.
+const SEALED_STATE_FILE: &str = ".state.sealed";
+
 pub struct Controller {
     kerl: KERL,
     tel: Tel,
+    backers: Vec<IdentifierPrefix>,
+    backer_threshold: u64,
+    oobi: OobiManager,
+    /// How [`Controller::collect_receipts`]/[`Controller::poll_backer_mailboxes`]
+    /// actually reach a backer's resolved OOBI endpoint. Defaults to
+    /// [`witness::NullTransport`], which reaches no one; plug in a real one
+    /// with [`Controller::with_transport`].
+    transport: Box<dyn witness::BackerTransport>,
+    /// Derivation code [`Controller::contribute_signature`] encodes its
+    /// signatures with. Defaults to `Ed25519Sha512`, the only scheme
+    /// [`keri::signer::CryptoBox`] produces, but a `KeyManager` backed by a
+    /// different scheme plugs its own in via [`Controller::with_signing_scheme`]
+    /// rather than this being a bare assumption buried in the signing code.
+    signing_scheme: SelfSigning,
+    receipts: ReceiptStore,
+    /// Data-encryption key derived from the controller's passphrase, used to
+    /// seal records this controller persists directly (the witness-receipt
+    /// store and, when encryption-at-rest is enabled, the KEL/TEL snapshot).
+    /// `None` when the controller was opened without a passphrase, i.e.
+    /// encryption-at-rest is disabled.
+    dek: Option<[u8; 32]>,
+    receipts_db: Option<sled::Db>,
+    /// Path the encrypted KEL/TEL snapshot is sealed to when `dek` is set.
+    /// `None` when encryption-at-rest is disabled, in which case the KEL/TEL
+    /// sled stores at the paths passed to [`Controller::new`] hold the
+    /// events directly, as plaintext.
+    sealed_state_path: Option<PathBuf>,
+    /// Keeps the live KEL/TEL sled stores' directories alive for the life of
+    /// the controller when encryption-at-rest is enabled. These directories
+    /// are process-local and temporary, not the durable copy of the state
+    /// (that's `sealed_state_path`); they're removed automatically when the
+    /// controller is dropped, so KEL/TEL event records never persist in
+    /// plaintext past the life of the process.
+    live_dirs: Option<(tempfile::TempDir, tempfile::TempDir)>,
+    policy: AccessPolicy,
+    unlocked: bool,
 }
 
 impl Controller {
-    pub fn new(root: &Path, tel_db: &Path) -> Self {
-        let db = SledEventDatabase::new(root).unwrap();
-        let tel_db = EventDatabase::new(tel_db).unwrap();
+    pub fn new(root: &Path, tel_db: &Path, passphrase: Option<&str>) -> Self {
+        let tel_db_path = tel_db;
+
+        let (dek, receipts_db, sealed_state_path, live_dirs, kel_live_path, tel_live_path) =
+            match passphrase {
+                Some(passphrase) => {
+                    let salt = Self::load_or_create_kdf_salt(root).unwrap();
+                    let dek = crypto::derive_key(passphrase, &salt).unwrap();
+                    let receipts_db = sled::open(tel_db_path.join("receipts")).unwrap();
+                    let kel_live = tempfile::Builder::new()
+                        .prefix("sadv-kel-live")
+                        .tempdir()
+                        .unwrap();
+                    let tel_live = tempfile::Builder::new()
+                        .prefix("sadv-tel-live")
+                        .tempdir()
+                        .unwrap();
+                    let kel_live_path = kel_live.path().to_path_buf();
+                    let tel_live_path = tel_live.path().to_path_buf();
+                    (
+                        Some(dek),
+                        Some(receipts_db),
+                        Some(root.join(SEALED_STATE_FILE)),
+                        Some((kel_live, tel_live)),
+                        kel_live_path,
+                        tel_live_path,
+                    )
+                }
+                None => (None, None, None, None, root.to_path_buf(), tel_db.to_path_buf()),
+            };
+
+        let db = SledEventDatabase::new(&kel_live_path).unwrap();
+        let tel_event_db = EventDatabase::new(&tel_live_path).unwrap();
         let tel = Tel::new(
-            tel_db,
+            tel_event_db,
             keri::event::SerializationFormats::JSON,
             SelfAddressing::Blake3_256,
         );
 
-        Controller {
+        let mut controller = Controller {
             kerl: KERL::new(db, IdentifierPrefix::default()).unwrap(),
             tel,
+            backers: vec![],
+            backer_threshold: 0,
+            oobi: OobiManager::new(),
+            transport: Box::new(witness::NullTransport),
+            signing_scheme: SelfSigning::Ed25519Sha512,
+            receipts: ReceiptStore::new(),
+            dek,
+            receipts_db,
+            sealed_state_path,
+            live_dirs,
+            policy: AccessPolicy::Open,
+            unlocked: true,
+        };
+        controller.load_persisted_receipts().unwrap();
+        controller.load_sealed_state().unwrap();
+        controller
+    }
+
+    /// Loads the encrypted KEL/TEL snapshot sealed by a previous session (if
+    /// any yet exists) and replays it into this session's live, ephemeral
+    /// KEL/TEL sled stores. No-op for controllers opened without a
+    /// passphrase, or the first time a controller is opened at `root`.
+    fn load_sealed_state(&mut self) -> Result<(), Error> {
+        let (dek, path) = match (&self.dek, &self.sealed_state_path) {
+            (Some(dek), Some(path)) => (*dek, path.clone()),
+            _ => return Ok(()),
+        };
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(_) => return Ok(()),
+        };
+        let sealed: crypto::SealedRecord =
+            serde_json::from_slice(&bytes).map_err(|e| Error::Generic(e.to_string()))?;
+        let plaintext = crypto::unseal(&dek, &sealed)?;
+        let state: facade::ControllerState =
+            serde_json::from_slice(&plaintext).map_err(|e| Error::Generic(e.to_string()))?;
+        facade::replay_state(self, state)
+    }
+
+    /// Captures this controller's entire KEL/TEL state and seals it to
+    /// `sealed_state_path` under the data-encryption key. Called after
+    /// every operation that commits a KEL or TEL event, so the only
+    /// cross-process-durable copy of KEL/TEL event records is AES-GCM
+    /// sealed; the plaintext sled stores backing the live session exist
+    /// only in a process-local temporary directory. No-op for controllers
+    /// opened without a passphrase.
+    fn persist_sealed_state(&self) -> Result<(), Error> {
+        let (dek, path) = match (&self.dek, &self.sealed_state_path) {
+            (Some(dek), Some(path)) => (dek, path),
+            _ => return Ok(()),
+        };
+        let state = facade::capture_state(self)?;
+        let plaintext = serde_json::to_vec(&state).map_err(|e| Error::Generic(e.to_string()))?;
+        let sealed = crypto::seal(dek, &plaintext)?;
+        let sealed_bytes =
+            serde_json::to_vec(&sealed).map_err(|e| Error::Generic(e.to_string()))?;
+        fs::write(path, sealed_bytes).map_err(|e| Error::Generic(e.to_string()))
+    }
+
+    /// Reads the passphrase KDF salt from `root`, creating and persisting a
+    /// fresh random one the first time a controller is opened there.
+    fn load_or_create_kdf_salt(root: &Path) -> Result<[u8; 16], Error> {
+        fs::create_dir_all(root).map_err(|e| Error::Generic(e.to_string()))?;
+        let path = root.join(KDF_SALT_FILE);
+        if let Ok(bytes) = fs::read(&path) {
+            if bytes.len() != 16 {
+                return Err(Error::Generic(format!(
+                    "Corrupt KDF salt file at {}: expected 16 bytes, found {}",
+                    path.display(),
+                    bytes.len()
+                )));
+            }
+            let mut salt = [0u8; 16];
+            salt.copy_from_slice(&bytes);
+            Ok(salt)
+        } else {
+            let mut salt = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut salt);
+            fs::write(&path, salt).map_err(|e| Error::Generic(e.to_string()))?;
+            Ok(salt)
         }
     }
 
@@ -42,13 +213,111 @@ impl Controller {
         km: &K,
         backers: Option<Vec<IdentifierPrefix>>,
         backer_threshold: u64,
+        passphrase: Option<&str>,
     ) -> Result<Self, Error> {
-        let mut controller = Controller::new(kel_db_path, tel_db_path);
+        let mut controller = Controller::new(kel_db_path, tel_db_path, passphrase);
         controller.incept_kel(km)?;
         controller.incept_tel(km, backers, backer_threshold)?;
         Ok(controller)
     }
 
+    /// Rehydrates a [`Controller`] whose databases were opened with a
+    /// passphrase, and decrypts the signing key material exported to
+    /// `keystore_path` by [`Controller::export_keystore`]. The caller is
+    /// responsible for constructing a [`KeyManager`] from the returned
+    /// secret bytes; `Controller` does not hold keys itself.
+    pub fn unlock(
+        kel_db_path: &Path,
+        tel_db_path: &Path,
+        keystore_path: &Path,
+        passphrase: &str,
+    ) -> Result<(Self, Vec<u8>), Error> {
+        let secret = keystore::import_from_file(keystore_path, passphrase)?;
+        let mut controller = Controller::new(kel_db_path, tel_db_path, Some(passphrase));
+        controller.unlocked = true;
+        Ok((controller, secret))
+    }
+
+    /// Encrypts `secret` (raw signing key material) under `passphrase` and
+    /// writes it to `path`, in the style of an Ethereum JSON keystore file.
+    pub fn export_keystore(secret: &[u8], path: &Path, passphrase: &str) -> Result<(), Error> {
+        keystore::export_to_file(path, secret, passphrase)
+    }
+
+    /// Sets the access policy gating `issue`/`revoke`/`rotate`. Switching to
+    /// [`AccessPolicy::RequireUnlock`] immediately locks the controller;
+    /// call [`Controller::unlock`] (or construct via it) to unlock again.
+    pub fn with_policy(mut self, policy: AccessPolicy) -> Self {
+        if policy == AccessPolicy::RequireUnlock {
+            self.unlocked = false;
+        }
+        self.policy = policy;
+        self
+    }
+
+    /// Plugs in the transport [`Controller::collect_receipts`]/
+    /// [`Controller::poll_backer_mailboxes`] use to actually reach a
+    /// backer's resolved OOBI endpoint, replacing the default
+    /// [`witness::NullTransport`] (which reaches no one).
+    pub fn with_transport(mut self, transport: Box<dyn witness::BackerTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Sets the derivation code [`Controller::contribute_signature`] encodes
+    /// its signatures with, for a `KeyManager` that doesn't produce
+    /// `Ed25519Sha512` signatures.
+    pub fn with_signing_scheme(mut self, scheme: SelfSigning) -> Self {
+        self.signing_scheme = scheme;
+        self
+    }
+
+    fn require_unlocked(&self) -> Result<(), Error> {
+        self.policy.check(self.unlocked)
+    }
+
+    /// Persists the receipts gathered so far for `digest`, sealed under the
+    /// controller's data-encryption key. No-op when the controller was
+    /// opened without a passphrase.
+    fn persist_receipts(&self, digest: &SelfAddressingPrefix) -> Result<(), Error> {
+        if let (Some(dek), Some(db)) = (&self.dek, &self.receipts_db) {
+            let receipts = self.receipts.get_receipts(digest);
+            let plaintext =
+                serde_json::to_vec(&receipts).map_err(|e| Error::Generic(e.to_string()))?;
+            let sealed = crypto::seal(dek, &plaintext)?;
+            let sealed_bytes =
+                serde_json::to_vec(&sealed).map_err(|e| Error::Generic(e.to_string()))?;
+            db.insert(digest.to_str().as_bytes(), sealed_bytes)
+                .map_err(|e| Error::Generic(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Loads whatever receipts were sealed to disk by earlier sessions back
+    /// into the in-memory receipt store.
+    fn load_persisted_receipts(&mut self) -> Result<(), Error> {
+        let (dek, db) = match (&self.dek, &self.receipts_db) {
+            (Some(dek), Some(db)) => (*dek, db.clone()),
+            _ => return Ok(()),
+        };
+        for entry in db.iter() {
+            let (key, value) = entry.map_err(|e| Error::Generic(e.to_string()))?;
+            let digest: SelfAddressingPrefix = std::str::from_utf8(&key)
+                .map_err(|e| Error::Generic(e.to_string()))?
+                .parse()
+                .map_err(|_| Error::Generic("Invalid persisted receipt digest".into()))?;
+            let sealed: crypto::SealedRecord =
+                serde_json::from_slice(&value).map_err(|e| Error::Generic(e.to_string()))?;
+            let plaintext = crypto::unseal(&dek, &sealed)?;
+            let receipts: Vec<WitnessReceipt> =
+                serde_json::from_slice(&plaintext).map_err(|e| Error::Generic(e.to_string()))?;
+            for receipt in receipts {
+                self.receipts.add_receipt(digest.clone(), receipt);
+            }
+        }
+        Ok(())
+    }
+
     /// Generate and process tel inception event for given backers and backer
     /// threshold. None in backers argument sets config to no backers.
     fn incept_tel<K: KeyManager>(
@@ -61,6 +330,9 @@ impl Controller {
             Some(backers) => (vec![], backers),
             None => (vec![Config::NoBackers], vec![]),
         };
+        self.backers = b.clone();
+        self.backer_threshold = backer_threshold;
+
         let vcp = self.tel.make_inception_event(
             self.kerl.get_state().unwrap().unwrap().prefix.clone(),
             config,
@@ -82,17 +354,92 @@ impl Controller {
             digest: SelfAddressing::Blake3_256.derive(&ixn.event_message.serialize()?),
         };
 
+        self.collect_receipts(&ixn_source_seal.digest, &ixn.event_message.serialize()?)?;
+
         // before applying vcp to management tel, insert anchor event seal to be able to verify that operation.
         let verifiable_vcp =
             VerifiableEvent::new(Event::Management(vcp.clone()), ixn_source_seal.into());
         self.tel.process(verifiable_vcp)?;
 
+        self.persist_sealed_state()?;
         Ok(())
     }
 
+    /// Dispatches the just-produced event to all configured backers and
+    /// folds whatever receipts come back into the receipt store.
+    fn collect_receipts(
+        &mut self,
+        event_digest: &SelfAddressingPrefix,
+        event_bytes: &[u8],
+    ) -> Result<(), Error> {
+        if self.backers.is_empty() {
+            return Ok(());
+        }
+        let receipts = witness::dispatch_to_backers(
+            self.transport.as_ref(),
+            &self.oobi,
+            &self.backers,
+            event_digest,
+            event_bytes,
+        )?;
+        for receipt in receipts {
+            self.receipts.add_receipt(event_digest.clone(), receipt);
+        }
+        self.persist_receipts(event_digest)
+    }
+
+    /// Registers the endpoint a backer can be reached at for receipt
+    /// collection.
+    pub fn resolve_oobi(&mut self, backer: IdentifierPrefix, url: String) {
+        self.oobi.resolve_oobi(backer, url);
+    }
+
+    /// Returns the receipts collected so far for the event with the given
+    /// digest.
+    pub fn get_receipts(&self, digest: &SelfAddressingPrefix) -> Vec<WitnessReceipt> {
+        self.receipts.get_receipts(digest)
+    }
+
+    /// Whether the event with the given digest has reached the backer
+    /// threshold configured at inception.
+    pub fn is_witnessed(&self, digest: &SelfAddressingPrefix) -> bool {
+        self.receipts.is_witnessed(digest, self.backer_threshold)
+    }
+
+    /// Retries fetching receipts for the event anchoring `message`'s most
+    /// recent TEL event from every configured backer's mailbox.
+    pub fn poll_backer_mailboxes(&mut self, message_hash: &SelfAddressingPrefix) -> Result<(), Error> {
+        let seal = self.last_source_seal(message_hash)?;
+        witness::MailboxPoll::new(self.transport.as_ref(), &self.oobi).poll_once(
+            &mut self.receipts,
+            &self.backers,
+            &seal.digest,
+        )
+    }
+
+    /// Accepts a witness receipt for `event_digest` obtained out-of-band,
+    /// e.g. by the embedding application's own transport delivering a
+    /// backer's callback, verifying it before folding it into the receipt
+    /// store. `dispatch_to_backers` only reaches backers with a resolved
+    /// OOBI at the moment an event is produced, so this is how a receipt
+    /// that arrives later, or through a transport this controller doesn't
+    /// drive itself, ever counts toward `backer_threshold`.
+    pub fn submit_receipt(
+        &mut self,
+        event_digest: &SelfAddressingPrefix,
+        receipt: WitnessReceipt,
+    ) -> Result<(), Error> {
+        if !receipt.verify(event_digest)? {
+            return Err(Error::Generic("Invalid witness receipt signature".into()));
+        }
+        self.receipts.add_receipt(event_digest.clone(), receipt);
+        self.persist_receipts(event_digest)
+    }
+
     // Generate and process kel inception event.
     fn incept_kel<K: KeyManager>(&mut self, km: &K) -> Result<(), Error> {
         self.kerl.incept(km)?;
+        self.persist_sealed_state()?;
         Ok(())
     }
 
@@ -117,17 +464,37 @@ impl Controller {
 
         let ixn_source_seal = EventSourceSeal {
             sn: ixn.event_message.event.sn,
-            digest: SelfAddressing::Blake3_256.derive(&ixn.serialize()?),
+            digest: SelfAddressing::Blake3_256.derive(&ixn.event_message.serialize()?),
         };
 
+        self.backers = ba
+            .iter()
+            .cloned()
+            .chain(self.backers.iter().filter(|b| !br.contains(b)).cloned())
+            .collect();
+        self.collect_receipts(&ixn_source_seal.digest, &ixn.event_message.serialize()?)?;
+
         // before applying vcp to management tel, insert anchor event seal to be able to verify that operation.
         let verifiable_rcp =
             VerifiableEvent::new(Event::Management(rcp.clone()), ixn_source_seal.into());
         self.tel.process(verifiable_rcp.clone())?;
+        self.persist_sealed_state()?;
         Ok(())
     }
 
-    pub fn issue<K: KeyManager>(&mut self, message: &str, km: &K) -> Result<Vec<u8>, Error> {
+    /// Issues `message`, anchoring it in the TEL/KEL as before, and returns
+    /// `km`'s contribution to the issuer's signature set: its signature at
+    /// `key_index` in the current key state's `public_keys`. Callers
+    /// controlling more than one key gather the other contributions with
+    /// [`Controller::contribute_signature`] and pass the combined set to
+    /// [`Controller::verify`].
+    pub fn issue<K: KeyManager>(
+        &mut self,
+        message: &str,
+        key_index: u16,
+        km: &K,
+    ) -> Result<Vec<IndexedSignature>, Error> {
+        self.require_unlocked()?;
         let iss = self.tel.make_issuance_event(message)?;
         // create vcp seal which will be inserted into issuer kel (ixn event)
         let iss_seal = Seal::Event(EventSeal {
@@ -143,12 +510,36 @@ impl Controller {
             digest: SelfAddressing::Blake3_256.derive(&ixn.event_message.serialize()?),
         };
 
+        self.collect_receipts(&ixn_source_seal.digest, &ixn.event_message.serialize()?)?;
+
         let verifiable_vcp = VerifiableEvent::new(Event::Vc(iss.clone()), ixn_source_seal.into());
         self.tel.process(verifiable_vcp.clone())?;
-        km.sign(&message.as_bytes().to_vec()).map_err(|e| e.into())
+        self.persist_sealed_state()?;
+
+        Ok(vec![self.contribute_signature(message, key_index, km)?])
+    }
+
+    /// Produces `km`'s signature over `message` at `key_index`, without
+    /// touching the KEL/TEL. This is the building block multi-controller
+    /// issuance uses: every controller sharing the identifier calls this
+    /// with its own key manager and index, and the resulting signatures are
+    /// combined and checked against the `SignatureThreshold` by
+    /// [`Controller::verify`].
+    pub fn contribute_signature<K: KeyManager>(
+        &self,
+        message: &str,
+        key_index: u16,
+        km: &K,
+    ) -> Result<IndexedSignature, Error> {
+        let raw = km.sign(&message.as_bytes().to_vec())?;
+        Ok(IndexedSignature {
+            index: key_index,
+            signature: self.signing_scheme.derive(raw),
+        })
     }
 
     pub fn revoke<K: KeyManager>(&mut self, message: &str, km: &K) -> Result<(), Error> {
+        self.require_unlocked()?;
         let message_id = SelfAddressing::Blake3_256.derive(message.as_bytes());
         let rev_event = self.tel.make_revoke_event(&message_id)?;
         // create rev seal which will be inserted into issuer kel (ixn event)
@@ -163,23 +554,38 @@ impl Controller {
         // Make source seal.
         let ixn_source_seal = EventSourceSeal {
             sn: ixn.event_message.event.sn,
-            digest: SelfAddressing::Blake3_256.derive(&ixn.serialize()?),
+            digest: SelfAddressing::Blake3_256.derive(&ixn.event_message.serialize()?),
         };
 
+        self.collect_receipts(&ixn_source_seal.digest, &ixn.event_message.serialize()?)?;
+
         let verifiable_rev =
             VerifiableEvent::new(Event::Vc(rev_event.clone()), ixn_source_seal.into());
 
         self.tel.process(verifiable_rev.clone())?;
+        self.persist_sealed_state()?;
         Ok(())
     }
 
     pub fn rotate<K: KeyManager>(&self, km: &K) -> Result<(), Error> {
+        self.require_unlocked()?;
         self.kerl.rotate(km)?;
+        self.persist_sealed_state()?;
         Ok(())
     }
 
-    /// Check the state of message of given digest.
-    pub fn get_vc_state(&self, hash: &SelfAddressingPrefix) -> Result<TelState, Error> {
+    /// Check the state of message of given digest. If `require_witnessed` is
+    /// set, the event anchoring the VC's last TEL event must have reached
+    /// the configured backer threshold, or this returns an error instead of
+    /// the state.
+    pub fn get_vc_state(
+        &self,
+        hash: &SelfAddressingPrefix,
+        require_witnessed: bool,
+    ) -> Result<TelState, Error> {
+        if require_witnessed {
+            self.check_witnessed(hash)?;
+        }
         self.tel.get_vc_state(hash).map_err(|e| e.into())
     }
 
@@ -187,22 +593,88 @@ impl Controller {
         self.tel.get_tel(hash)
     }
 
+    /// Returns the digest of the KEL interaction event anchoring the last
+    /// TEL event of the message with the given hash.
+    ///
+    /// This trusts `get_tel`'s ordering for the common case of reading the
+    /// current state; call [`Controller::verify_log`] for a full audit pass
+    /// that re-derives the chain instead of trusting storage order.
+    fn last_source_seal(&self, message_hash: &SelfAddressingPrefix) -> Result<EventSourceSeal, Error> {
+        self.tel
+            .get_tel(message_hash)?
+            .last()
+            .ok_or_else(|| Error::Generic("No events in tel".into()))
+            .map(|event| event.seal.seal.clone())
+    }
+
+    /// Walks the entire KEL and every credential's TEL from genesis,
+    /// re-deriving each event's digest to confirm the KEL's prior-digest
+    /// links are unbroken, that every TEL event's source seal actually
+    /// resolves to the KEL interaction event it claims to anchor to, and
+    /// that each credential's issue/revoke ordering is monotonic. Returns a
+    /// structured report of the first inconsistency found, turning the
+    /// per-message `get_vc_state`/`verify` checks into a full audit able to
+    /// detect tampering or reordering anywhere in the logs.
+    pub fn verify_log(&self) -> Result<AuditReport, Error> {
+        let kel = self.kerl.get_kerl()?;
+        let mut report = AuditReport::default();
+
+        let mut prev_digest: Option<SelfAddressingPrefix> = None;
+        for event in &kel {
+            report.kel_events_checked += 1;
+            if let Some(prev) = &prev_digest {
+                if &event.event_message.event.prior_digest != prev {
+                    report.first_inconsistency = Some(Inconsistency::BrokenKelLink {
+                        sn: event.event_message.event.sn,
+                    });
+                    return Ok(report);
+                }
+            }
+            prev_digest = Some(SelfAddressing::Blake3_256.derive(&event.event_message.serialize()?));
+        }
+
+        for vc in self.tel.get_vc_digests()? {
+            let events = self.tel.get_tel(&vc)?;
+            for event in &events {
+                if !audit::anchors_to(&kel, &event.seal.seal)? {
+                    report.tel_events_checked += events.len();
+                    report.first_inconsistency = Some(Inconsistency::UnanchoredTelEvent {
+                        vc: vc.clone(),
+                        sn: event.seal.seal.sn,
+                    });
+                    return Ok(report);
+                }
+            }
+
+            let slice_report = audit::verify_slice(&events);
+            report.tel_events_checked += slice_report.tel_events_checked;
+            if let Some(inconsistency) = slice_report.first_inconsistency {
+                report.first_inconsistency = Some(inconsistency);
+                return Ok(report);
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn check_witnessed(&self, message_hash: &SelfAddressingPrefix) -> Result<(), Error> {
+        let seal = self.last_source_seal(message_hash)?;
+        if self.is_witnessed(&seal.digest) {
+            Ok(())
+        } else {
+            Err(Error::Generic(
+                "Event not yet witnessed by enough backers".into(),
+            ))
+        }
+    }
+
     /// Returns keys that was used to sign message of given hash. Returns error,
     /// if message was revoked or not yet issued.
     pub fn get_pub_key(
         &self,
         message_hash: SelfAddressingPrefix,
     ) -> Result<Vec<BasicPrefix>, Error> {
-        // Get last event vc event and its source seal.
-        let source_seal: EventSourceSeal = self
-            .tel
-            .get_tel(&message_hash)?
-            // TODO what if events are out of order?
-            .last()
-            .ok_or(Error::Generic("No events in tel".into()))?
-            .seal
-            .seal
-            .clone();
+        let source_seal = self.last_source_seal(&message_hash)?;
 
         let k = self.kerl.get_state_for_seal(
             &self.tel.get_issuer()?,
@@ -215,17 +687,45 @@ impl Controller {
         }
     }
 
-    /// Verify signature for given message.
-    pub fn verify(&self, message: &str, signature: &[u8]) -> Result<bool, Error> {
+    /// Verify the signature set for given message. Each signature is
+    /// checked against the public key at its own index in the issuer's key
+    /// state as of the KEL event anchoring the VC's last TEL event (so a
+    /// signature made before a later rotation still verifies); the set is
+    /// accepted only once the indices with a
+    /// valid signature satisfy the key state's `SignatureThreshold`
+    /// (including weighted/fractional thresholds). If `require_witnessed`
+    /// is set, the issuance event must also have reached the configured
+    /// backer threshold.
+    pub fn verify(
+        &self,
+        message: &str,
+        signatures: &[IndexedSignature],
+        require_witnessed: bool,
+    ) -> Result<bool, Error> {
         let message_hash = SelfAddressing::Blake3_256.derive(message.as_bytes());
-        match self.get_vc_state(&message_hash)? {
+        match self.get_vc_state(&message_hash, require_witnessed)? {
             TelState::NotIsuued => Err(Error::Generic("Not yet issued".into())),
             TelState::Issued(_) => {
-                let key = self.get_pub_key(message_hash)?;
-                Ok(key.into_iter().fold(true, |acc, k| {
-                    let sspref = SelfSigning::Ed25519Sha512.derive(signature.to_vec());
-                    acc && k.verify(message.as_bytes(), &sspref).unwrap()
-                }))
+                let source_seal = self.last_source_seal(&message_hash)?;
+                let state = self
+                    .kerl
+                    .get_state_for_seal(&self.tel.get_issuer()?, source_seal.sn, &source_seal.digest)?
+                    .ok_or_else(|| Error::Generic("No key data".into()))?;
+
+                // Distinct indices only: repeating one key's signature must
+                // not be able to stand in for several different keys when
+                // checked against the threshold.
+                let mut satisfied_indices = vec![];
+                for sig in signatures {
+                    let key = state.current.public_keys.get(sig.index as usize).ok_or_else(|| {
+                        Error::Generic(format!("No key at index {}", sig.index))
+                    })?;
+                    if sig.verify(message.as_bytes(), key)? && !satisfied_indices.contains(&sig.index) {
+                        satisfied_indices.push(sig.index);
+                    }
+                }
+
+                Ok(state.current.threshold.enough_signatures(&satisfied_indices))
             }
             TelState::Revoked => Err(Error::Generic("VC was revoked".into())),
         }
@@ -252,7 +752,8 @@ mod test {
 
         let message = "some vc";
 
-        let mut issuer = Controller::init(root.path(), tel_root.path(), &km, Some(vec![]), 0)?;
+        let mut issuer =
+            Controller::init(root.path(), tel_root.path(), &km, Some(vec![]), 0, None)?;
 
         // Chcek if tel inception event is in db.
         let o = issuer.tel.get_management_events()?;
@@ -260,26 +761,26 @@ mod test {
 
         let message_hash = SelfAddressing::Blake3_256.derive(message.as_bytes());
 
-        let signature = issuer.issue(message, &km)?;
-        let verification_result = issuer.verify(message, &signature);
+        let signature = issuer.issue(message, 0, &km)?;
+        let verification_result = issuer.verify(message, &signature, false);
         assert!(matches!(verification_result, Ok(true)));
 
         // Chcek if iss event is in db.
         let o = issuer.get_tel(&message_hash)?;
         assert_eq!(o.len(), 1);
 
-        let state = issuer.get_vc_state(&message_hash)?;
+        let state = issuer.get_vc_state(&message_hash, false)?;
         assert!(matches!(state, TelState::Issued(_)));
 
         // Try to verify message after key rotation.
         km.rotate()?;
         issuer.rotate(&km)?;
 
-        let verification_result = issuer.verify(message, &signature);
+        let verification_result = issuer.verify(message, &signature, false);
         assert!(matches!(verification_result, Ok(true)));
 
         issuer.revoke(message, &km)?;
-        let state = issuer.get_vc_state(&message_hash)?;
+        let state = issuer.get_vc_state(&message_hash, false)?;
         assert!(matches!(state, TelState::Revoked));
 
         // Check if revoke event is in db.
@@ -287,7 +788,7 @@ mod test {
         assert_eq!(o.len(), 2);
 
         // Message verification should return error, because it was revoked.
-        let verification_result = issuer.verify(message, &signature);
+        let verification_result = issuer.verify(message, &signature, false);
         assert!(verification_result.is_err());
 
         Ok(())
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Governs whether a [`super::Controller`] may perform signing operations
+/// before it has been unlocked.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum AccessPolicy {
+    /// No gating: `issue`/`revoke`/`rotate` are always allowed.
+    Open,
+    /// `issue`/`revoke`/`rotate` are refused until the controller has been
+    /// unlocked with its passphrase (see [`super::Controller::unlock`]).
+    RequireUnlock,
+}
+
+impl Default for AccessPolicy {
+    fn default() -> Self {
+        AccessPolicy::Open
+    }
+}
+
+impl AccessPolicy {
+    pub(super) fn check(&self, unlocked: bool) -> Result<(), Error> {
+        match self {
+            AccessPolicy::Open => Ok(()),
+            AccessPolicy::RequireUnlock if unlocked => Ok(()),
+            AccessPolicy::RequireUnlock => {
+                Err(Error::Generic("Controller is locked; call unlock() first".into()))
+            }
+        }
+    }
+}
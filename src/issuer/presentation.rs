@@ -0,0 +1,258 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use keri::{
+    derivation::self_addressing::SelfAddressing,
+    event::{event_data::EventData, sections::key_config::KeyConfig},
+    event_message::SignedEventMessage,
+    prefix::{IdentifierPrefix, SelfAddressingPrefix},
+    signer::KeyManager,
+};
+use teliox::{event::verifiable_event::VerifiableEvent, state::vc_state::TelState};
+
+use crate::error::Error;
+
+use super::{audit, Controller, IndexedSignature};
+
+/// Human-readable armor tag, mirroring the hrp+separator idea behind
+/// bech32: readers can tell what kind of blob this is before decoding it.
+const ARMOR_TAG: &str = "sadv1cred";
+
+/// A self-contained, portable bundle proving a credential's state without
+/// access to the issuer's KEL/TEL databases: the credential message, the
+/// full TEL slice for its digest, the issuer's entire KEL (so a verifier can
+/// both confirm its hash chain and resolve the latest establishment event's
+/// key config, which the signature set is checked against), and the
+/// issuer's signature over the message.
+#[derive(Serialize, Deserialize)]
+pub struct CredentialPresentation {
+    pub message: String,
+    pub tel: Vec<VerifiableEvent>,
+    pub kel: Vec<SignedEventMessage>,
+    pub signatures: Vec<IndexedSignature>,
+}
+
+impl CredentialPresentation {
+    /// Ascii-armors this presentation: a bech32-style human readable tag
+    /// followed by the base64-encoded, JSON-serialized payload.
+    pub fn to_armored(&self) -> Result<String, Error> {
+        let payload = serde_json::to_vec(self).map_err(|e| Error::Generic(e.to_string()))?;
+        Ok(format!("{}{}", ARMOR_TAG, STANDARD.encode(payload)))
+    }
+
+    /// Parses a bundle produced by [`CredentialPresentation::to_armored`].
+    pub fn from_armored(armored: &str) -> Result<Self, Error> {
+        let body = armored
+            .strip_prefix(ARMOR_TAG)
+            .ok_or_else(|| Error::Generic("Unrecognized presentation armor".into()))?;
+        let payload = STANDARD
+            .decode(body)
+            .map_err(|e| Error::Generic(e.to_string()))?;
+        serde_json::from_slice(&payload).map_err(|e| Error::Generic(e.to_string()))
+    }
+}
+
+impl Controller {
+    /// Produces a self-contained presentation of `message`'s credential
+    /// state: the TEL slice for its digest plus the issuer's entire KEL,
+    /// signed and packaged so a third party can validate it standalone,
+    /// without touching this controller's databases.
+    pub fn present<K: KeyManager>(
+        &self,
+        message: &str,
+        key_index: u16,
+        km: &K,
+    ) -> Result<CredentialPresentation, Error> {
+        let message_hash = SelfAddressing::Blake3_256.derive(message.as_bytes());
+        let tel = self.get_tel(&message_hash)?;
+        let kel = self.kerl.get_kerl()?;
+        let signature = self.contribute_signature(message, key_index, km)?;
+
+        Ok(CredentialPresentation {
+            message: message.to_string(),
+            tel,
+            kel,
+            signatures: vec![signature],
+        })
+    }
+}
+
+/// Returns the key configuration (public keys and signature threshold) an
+/// establishment event (inception or rotation) put in force.
+fn established_key_config(event: &SignedEventMessage) -> Result<KeyConfig, Error> {
+    match &event.event_message.event.event_data {
+        EventData::Icp(icp) => Ok(icp.key_config.clone()),
+        EventData::Rot(rot) => Ok(rot.key_config.clone()),
+        _ => Err(Error::Generic(
+            "Anchor event is not an establishment event".into(),
+        )),
+    }
+}
+
+/// Returns the key configuration of the latest establishment event
+/// (inception or rotation) in `kel`. [`Controller::present`] signs with
+/// whatever key manager the caller hands it *now*, which reflects this key
+/// config rather than whichever one was in force back when the credential
+/// was issued, so this is what a presentation's signature set must be
+/// checked against — not the issuance-time key config.
+fn latest_key_config(kel: &[SignedEventMessage]) -> Result<KeyConfig, Error> {
+    kel.iter()
+        .rev()
+        .find_map(|event| established_key_config(event).ok())
+        .ok_or_else(|| Error::Generic("No establishment event in bundled KEL".into()))
+}
+
+/// Validates a presentation bundle with no database access:
+///
+/// 1. Confirms the bundled KEL's prior-digest links are unbroken and that it
+///    starts with an inception event, taking that event's self-certifying
+///    prefix as the issuer identifier.
+/// 2. Confirms the bundled TEL resolves to `Issued` (not `Revoked`).
+/// 3. Confirms the issuing event's source seal actually resolves to a KEL
+///    interaction event in the bundled KEL (the same check
+///    [`super::Controller::verify_log`] runs against a live database).
+/// 4. Resolves the *current* key configuration (the latest establishment
+///    event in the bundled KEL — [`Controller::present`] always signs under
+///    whichever key is active when it's called, not the issuance-time key)
+///    and verifies the bundled signature set against it, accepting only
+///    once it satisfies the key config's `SignatureThreshold`.
+///
+/// Returns the issuer's identifier on success, so a caller can anchor trust
+/// to a specific identifier rather than whatever key config the bundle
+/// happened to carry.
+pub fn verify_presentation(bundle: &CredentialPresentation) -> Result<IdentifierPrefix, Error> {
+    let inception = bundle
+        .kel
+        .first()
+        .ok_or_else(|| Error::Generic("Empty KEL bundle".into()))?;
+    if !matches!(inception.event_message.event.event_data, EventData::Icp(_)) {
+        return Err(Error::Generic(
+            "Bundled KEL does not start with an inception event".into(),
+        ));
+    }
+    let issuer = inception.event_message.event.prefix.clone();
+
+    let mut prev_digest: Option<SelfAddressingPrefix> = None;
+    for event in &bundle.kel {
+        if let Some(prev) = &prev_digest {
+            if &event.event_message.event.prior_digest != prev {
+                return Err(Error::Generic("Bundled KEL hash chain is broken".into()));
+            }
+        }
+        prev_digest = Some(SelfAddressing::Blake3_256.derive(&event.event_message.serialize()?));
+    }
+
+    let mut state = TelState::NotIsuued;
+    for event in &bundle.tel {
+        state = crate::tel::Tel::fold_state(state, event)?;
+    }
+
+    match state {
+        TelState::Issued(_) => {}
+        TelState::Revoked => return Err(Error::Generic("VC was revoked".into())),
+        TelState::NotIsuued => return Err(Error::Generic("VC not issued in bundle".into())),
+    }
+
+    let issuing_seal = bundle
+        .tel
+        .last()
+        .ok_or_else(|| Error::Generic("Empty TEL bundle".into()))?
+        .seal
+        .seal
+        .clone();
+
+    if !audit::anchors_to(&bundle.kel, &issuing_seal)? {
+        return Err(Error::Generic(
+            "TEL source seal does not resolve to a KEL event in the bundled KEL".into(),
+        ));
+    }
+
+    let key_config = latest_key_config(&bundle.kel)?;
+    // Distinct indices only: repeating one key's signature must not be able
+    // to stand in for several different keys when checked against the
+    // threshold.
+    let mut satisfied_indices = vec![];
+    for sig in &bundle.signatures {
+        let key = key_config
+            .public_keys
+            .get(sig.index as usize)
+            .ok_or_else(|| Error::Generic(format!("No key at index {}", sig.index)))?;
+        if sig.verify(bundle.message.as_bytes(), key)? && !satisfied_indices.contains(&sig.index) {
+            satisfied_indices.push(sig.index);
+        }
+    }
+
+    if key_config.threshold.enough_signatures(&satisfied_indices) {
+        Ok(issuer)
+    } else {
+        Err(Error::Generic(
+            "Signature set does not satisfy the key config's threshold".into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use keri::{
+        derivation::self_addressing::SelfAddressing,
+        signer::{CryptoBox, KeyManager},
+    };
+
+    use super::verify_presentation;
+    use crate::{error::Error, issuer::Controller};
+
+    #[test]
+    pub fn test_present_verify_round_trip() -> Result<(), Error> {
+        use tempfile::Builder;
+        let root = Builder::new().prefix("presentation-test-db").tempdir().unwrap();
+        let tel_root = Builder::new()
+            .prefix("presentation-tel-test-db")
+            .tempdir()
+            .unwrap();
+        let km = CryptoBox::new()?;
+
+        let mut controller =
+            Controller::init(root.path(), tel_root.path(), &km, Some(vec![]), 0, None)?;
+
+        let message = "some vc";
+        controller.issue(message, 0, &km)?;
+
+        let bundle = controller.present(message, 0, &km)?;
+        let issuer = verify_presentation(&bundle)?;
+        assert_eq!(issuer, controller.kerl.get_state().unwrap().unwrap().prefix);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_present_verify_after_rotation() -> Result<(), Error> {
+        use tempfile::Builder;
+        let root = Builder::new()
+            .prefix("presentation-rotate-test-db")
+            .tempdir()
+            .unwrap();
+        let tel_root = Builder::new()
+            .prefix("presentation-rotate-tel-test-db")
+            .tempdir()
+            .unwrap();
+        let mut km = CryptoBox::new()?;
+
+        let mut controller =
+            Controller::init(root.path(), tel_root.path(), &km, Some(vec![]), 0, None)?;
+
+        let message = "some vc";
+        controller.issue(message, 0, &km)?;
+
+        // Presenting after a rotation must check the signature (made just
+        // now, under the rotated key) against the current key config, not
+        // whatever key config was in force back at issuance.
+        km.rotate()?;
+        controller.rotate(&km)?;
+
+        let bundle = controller.present(message, 0, &km)?;
+        let issuer = verify_presentation(&bundle)?;
+        assert_eq!(issuer, controller.kerl.get_state().unwrap().unwrap().prefix);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use keri::prefix::{BasicPrefix, Prefix, SelfSigningPrefix};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// A single controller's signature over an event, tagged with the index of
+/// the public key (in the issuer's current key state) it was produced
+/// with. Several of these combine into the set checked against a (possibly
+/// weighted) `SignatureThreshold`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexedSignature {
+    pub index: u16,
+    pub signature: SelfSigningPrefix,
+}
+
+impl IndexedSignature {
+    /// Verifies this signature against `message` using the given key,
+    /// which the caller is responsible for having looked up at `self.index`.
+    pub fn verify(&self, message: &[u8], key: &BasicPrefix) -> Result<bool, Error> {
+        key.verify(message, &self.signature).map_err(|e| e.into())
+    }
+}